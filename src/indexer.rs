@@ -3,22 +3,40 @@
 
 use anyhow::Result;
 use flate2::read::GzDecoder;
-use lindera::dictionary::{load_dictionary_from_kind, DictionaryKind};
+use lindera::dictionary::{
+    load_dictionary_from_kind, load_user_dictionary_from_csv, DictionaryKind,
+};
 use lindera::mode::Mode;
 use lindera::segmenter::Segmenter;
 use lindera_tantivy::tokenizer::LinderaTokenizer;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io;
-use std::io::{BufReader, Read, Write};
-use std::path::Path;
-use tantivy::schema::{Schema, TextFieldIndexing, TextOptions, INDEXED, STORED, TEXT};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tantivy::schema::{Schema, TextFieldIndexing, TextOptions, FAST, INDEXED, STORED, TEXT};
+use tantivy::tokenizer::TokenStream;
 use tantivy::{Index, TantivyDocument};
 use wana_kana::ConvertJapanese;
+use xml::attribute::OwnedAttribute;
 use xml::reader::XmlEvent;
 use xml::EventReader;
 use yansi::Paint;
 
-pub fn create_schema() -> Schema {
+/// The languages indexed by default: English only. Languages are keyed by the
+/// ISO 639-2 code used in the `xml:lang` attribute on `<gloss>`. English
+/// glosses carry no `xml:lang` attribute at all, so `"eng"` doubles as the
+/// default.
+pub const DEFAULT_LANGUAGES: &[&str] = &["eng"];
+
+/// Every language JMdict/JMdict_e ships glosses in, for callers who want a
+/// fully multilingual index rather than the English-only default.
+pub const ALL_LANGUAGES: &[&str] = &[
+    "eng", "dut", "fre", "ger", "hun", "rus", "slv", "spa", "swe",
+];
+
+pub fn create_schema(languages: &[&str]) -> Schema {
     let mut builder = Schema::builder();
 
     let jp_options = TextOptions::default()
@@ -33,41 +51,123 @@ pub fn create_schema() -> Schema {
     #[allow(clippy::redundant_clone)]
     builder.add_text_field("reading", jp_options.clone());
     builder.add_text_field("reading_romaji", TEXT | STORED);
+    // best `ke_pri`/`re_pri` rank seen on the entry; FAST so it can feed a
+    // query-time relevance boost.
+    builder.add_i64_field("frequency", STORED | FAST);
 
-    // sense fields
-    builder.add_text_field("meaning", TEXT | STORED);
+    // sense fields, one meaning field per indexed language
+    for lang in languages {
+        builder.add_text_field(&format!("meaning_{lang}"), TEXT | STORED);
+    }
     // part-of-speech
     builder.add_text_field("pos", TEXT | STORED);
     builder.add_text_field("field", TEXT | STORED);
 
+    // Tatoeba example sentences linked to this entry; "examples" holds the
+    // Japanese side (ja_JP-tokenized, so it's also searchable) and
+    // "examples_en" the aligned English translation, both multi-valued and
+    // kept in the same order.
+    #[allow(clippy::redundant_clone)]
+    builder.add_text_field("examples", jp_options.clone());
+    builder.add_text_field("examples_en", TEXT | STORED);
+
     builder.build()
 }
 
-pub fn create_index(schema: &Schema, path: &str, index: &Index) -> Result<()> {
-    setup_tokenizer(index)?;
+pub fn create_index(
+    schema: &Schema,
+    path: &str,
+    index: &Index,
+    scope: IndexScope,
+    examples: Option<&HashMap<String, Vec<Example>>>,
+    tokenizer_config: &TokenizerConfig,
+) -> Result<()> {
+    setup_tokenizer(index, tokenizer_config)?;
     let mut index_writer = index.writer(50_000_000)?;
     index_writer.delete_all_documents()?;
 
     let mut parser = create_parser(path)?;
     let schema_fields = extract_schema_fields(schema);
-    
-    let count = parse_xml_and_index(&mut parser, &mut index_writer, &schema_fields)?;
-    
+
+    let count = parse_xml_and_index(
+        &mut parser,
+        &mut index_writer,
+        &schema_fields,
+        scope,
+        examples,
+    )?;
+
     commit_index(index_writer, count)
 }
 
-fn setup_tokenizer(index: &Index) -> Result<()> {
-    let dictionary = load_dictionary_from_kind(DictionaryKind::IPADIC)?;
-    let segmenter = Segmenter::new(
-        Mode::Normal,
-        dictionary,
-        None, // No user dictionary
-    );
+/// Configures the `ja_JP` Lindera tokenizer: which bundled dictionary to
+/// segment against, the segmentation mode, and an optional user dictionary
+/// CSV for domain-specific terms.
+pub struct TokenizerConfig {
+    pub dictionary_kind: DictionaryKind,
+    /// `Mode::Normal` keeps compound words whole; `Mode::Decompose` also
+    /// splits them into their finer-grained parts, trading precision for
+    /// recall.
+    pub mode: Mode,
+    pub user_dictionary_path: Option<PathBuf>,
+}
+
+impl Default for TokenizerConfig {
+    fn default() -> Self {
+        Self {
+            dictionary_kind: DictionaryKind::IPADIC,
+            mode: Mode::Normal,
+            user_dictionary_path: None,
+        }
+    }
+}
+
+pub(crate) fn setup_tokenizer(index: &Index, config: &TokenizerConfig) -> Result<()> {
+    let dictionary = load_dictionary_from_kind(config.dictionary_kind)?;
+    let user_dictionary = config
+        .user_dictionary_path
+        .as_ref()
+        .map(|path| load_user_dictionary_from_csv(config.dictionary_kind, path))
+        .transpose()?;
+    let segmenter = Segmenter::new(config.mode, dictionary, user_dictionary);
     let lindera_tokenizer = LinderaTokenizer::from_segmenter(segmenter);
     index.tokenizers().register("ja_JP", lindera_tokenizer);
     Ok(())
 }
 
+/// A single token produced by the registered `ja_JP` tokenizer: its surface
+/// form, byte offsets into the input, and position in the token stream.
+#[derive(Serialize)]
+pub struct AnalyzedToken {
+    pub text: String,
+    pub offset_from: usize,
+    pub offset_to: usize,
+    pub position: usize,
+}
+
+/// Runs `text` through the `ja_JP` tokenizer registered on `index` and
+/// returns its token stream, useful for diagnosing why a query or document
+/// did or didn't segment the way you expected.
+pub fn analyze(index: &Index, text: &str) -> Result<Vec<AnalyzedToken>> {
+    let mut tokenizer = index
+        .tokenizers()
+        .get("ja_JP")
+        .ok_or_else(|| anyhow::anyhow!("ja_JP tokenizer is not registered on this index"))?;
+
+    let mut tokens = Vec::new();
+    let mut token_stream = tokenizer.token_stream(text);
+    while token_stream.advance() {
+        let token = token_stream.token();
+        tokens.push(AnalyzedToken {
+            text: token.text.clone(),
+            offset_from: token.offset_from,
+            offset_to: token.offset_to,
+            position: token.position,
+        });
+    }
+    Ok(tokens)
+}
+
 fn create_parser(path: &str) -> Result<EventReader<GzDecoder<BufReader<File>>>> {
     let file = File::open(path)?;
     let reader = BufReader::new(file);
@@ -80,66 +180,171 @@ struct SchemaFields {
     word: tantivy::schema::Field,
     reading: tantivy::schema::Field,
     reading_romaji: tantivy::schema::Field,
-    meaning: tantivy::schema::Field,
+    frequency: tantivy::schema::Field,
+    /// Per-language `meaning_<lang>` fields, keyed by the same ISO 639-2
+    /// code used in `xml:lang`.
+    meaning: HashMap<String, tantivy::schema::Field>,
     pos: tantivy::schema::Field,
     field: tantivy::schema::Field,
+    examples: tantivy::schema::Field,
+    examples_en: tantivy::schema::Field,
 }
 
 struct ParseContext {
-    glosses: Vec<String>,
+    /// Glosses for the sense currently being parsed, bucketed by language.
+    glosses: HashMap<String, Vec<String>>,
+    /// `xml:lang` of the `<gloss>` currently open, set on its `StartElement`
+    /// and consumed when it closes.
+    current_gloss_lang: String,
     poses: Vec<String>,
     fields: Vec<String>,
+    /// Highest `ke_pri`/`re_pri` rank seen so far on the current entry, 0 if
+    /// none.
+    priority: i64,
+    /// `keb`/`reb` surface forms seen so far on the current entry, used to
+    /// look up linked Tatoeba examples when the entry closes.
+    surface_forms: Vec<String>,
     current_entry: Option<TantivyDocument>,
     count: i32,
 }
 
+/// Index-wide scope: whether to keep every entry, or only ones JMdict marks
+/// as common (i.e. carrying at least one `ke_pri`/`re_pri` code).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum IndexScope {
+    All,
+    CommonOnly,
+}
+
+/// A Tatoeba example sentence linked to a dictionary entry: the Japanese
+/// sentence and its English translation.
+#[derive(Clone)]
+pub struct Example {
+    pub ja: String,
+    pub en: String,
+}
+
+/// The number of example sentences kept per surface form; callers get the
+/// shortest (and so easiest to read) matches.
+const MAX_EXAMPLES_PER_ENTRY: usize = 3;
+
+/// Maps a JMdict priority code (`news1`, `ichi1`, `spec1`, `gai1`, `nf01`..`nf48`, ...)
+/// to a relevance rank: higher is more frequent. `nfXX` codes count down from
+/// 48 (rarest) to 01 (most frequent); the primary `*1` tags outrank every
+/// `nf` bucket, and `*2`+ tags are a middling fallback.
+fn priority_rank(code: &str) -> i64 {
+    if let Some(n) = code.strip_prefix("nf").and_then(|n| n.parse::<i64>().ok()) {
+        return (49 - n).max(1);
+    }
+    match code {
+        "news1" | "ichi1" | "spec1" | "gai1" => 50,
+        _ => 10,
+    }
+}
+
+/// Collects up to `MAX_EXAMPLES_PER_ENTRY` example sentences linked to any
+/// of `surface_forms` (an entry's `keb`/`reb` values), shortest first.
+fn linked_examples<'a>(
+    surface_forms: &[String],
+    examples: Option<&'a HashMap<String, Vec<Example>>>,
+) -> Vec<&'a Example> {
+    let Some(examples) = examples else {
+        return Vec::new();
+    };
+
+    let mut matches: Vec<&Example> = surface_forms
+        .iter()
+        .filter_map(|form| examples.get(form))
+        .flatten()
+        .collect();
+    matches.sort_by_key(|example| example.ja.chars().count());
+    matches.dedup_by(|a, b| a.ja == b.ja);
+    matches.truncate(MAX_EXAMPLES_PER_ENTRY);
+    matches
+}
+
 fn extract_schema_fields(schema: &Schema) -> SchemaFields {
+    let meaning = schema
+        .fields()
+        .filter_map(|(field, entry)| {
+            entry
+                .name()
+                .strip_prefix("meaning_")
+                .map(|lang| (lang.to_string(), field))
+        })
+        .collect();
+
     SchemaFields {
         id: schema.get_field("id").unwrap(),
         word: schema.get_field("word").unwrap(),
         reading: schema.get_field("reading").unwrap(),
         reading_romaji: schema.get_field("reading_romaji").unwrap(),
-        meaning: schema.get_field("meaning").unwrap(),
+        frequency: schema.get_field("frequency").unwrap(),
+        meaning,
         pos: schema.get_field("pos").unwrap(),
         field: schema.get_field("field").unwrap(),
+        examples: schema.get_field("examples").unwrap(),
+        examples_en: schema.get_field("examples_en").unwrap(),
     }
 }
 
+/// Streams the JMdict XML, maintaining an explicit stack of open element
+/// names so handlers can tell e.g. a `<gloss>` inside `<sense>` apart from
+/// any other context, instead of keying off bare local names. Character
+/// data accumulates into `buffer`, which is cleared on every `StartElement`
+/// and flushed into `context` by `handle_end_element` when its owning
+/// element closes. This is fully streaming: nothing is buffered beyond the
+/// stack, the text of the element currently on top of it, and the entry and
+/// sense currently being built.
 fn parse_xml_and_index(
     parser: &mut EventReader<GzDecoder<BufReader<File>>>,
     index_writer: &mut tantivy::IndexWriter,
     schema_fields: &SchemaFields,
+    scope: IndexScope,
+    examples: Option<&HashMap<String, Vec<Example>>>,
 ) -> Result<i32> {
+    let mut stack: Vec<String> = Vec::new();
+    let mut buffer = String::new();
     let mut context = ParseContext {
-        glosses: Vec::new(),
+        glosses: HashMap::new(),
+        current_gloss_lang: String::new(),
         poses: Vec::new(),
         fields: Vec::new(),
+        priority: 0,
+        surface_forms: Vec::new(),
         current_entry: Some(tantivy::doc!()),
         count: 0,
     };
 
     while let Ok(e) = parser.next() {
         match e {
-            XmlEvent::StartElement { name, .. } => {
-                handle_start_element(
-                    &name.local_name,
-                    parser,
-                    &mut context,
-                    schema_fields,
-                );
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                buffer.clear();
+                handle_start_element(&name.local_name, &attributes, &mut context);
+                stack.push(name.local_name.clone());
+            }
+            XmlEvent::Characters(s) => {
+                buffer.push_str(&s);
             }
             XmlEvent::EndElement { name } => {
+                stack.pop();
                 if handle_end_element(
                     &name.local_name,
+                    &stack,
+                    &buffer,
                     &mut context,
                     index_writer,
                     schema_fields,
+                    scope,
+                    examples,
                 )? {
-                    // Clear collections for next sense
                     context.glosses.clear();
                     context.poses.clear();
                     context.fields.clear();
                 }
+                buffer.clear();
             }
             XmlEvent::EndDocument => break,
             _ => {}
@@ -151,66 +356,58 @@ fn parse_xml_and_index(
 
 fn handle_start_element(
     element_name: &str,
-    parser: &mut EventReader<GzDecoder<BufReader<File>>>,
+    attributes: &[OwnedAttribute],
     context: &mut ParseContext,
-    schema_fields: &SchemaFields,
 ) {
     match element_name {
         "entry" => {
             context.current_entry = Some(tantivy::doc!());
+            context.priority = 0;
+            context.surface_forms.clear();
         }
         "sense" => {
             context.glosses.clear();
             context.poses.clear();
             context.fields.clear();
         }
-        "ent_seq" => {
-            let entry_id = extract_next_string(parser);
-            context.current_entry
-                .as_mut()
-                .unwrap()
-                .add_i64(schema_fields.id, entry_id.parse::<i64>().unwrap());
-        }
-        "keb" => {
-            let keb = extract_next_string(parser);
-            context.current_entry.as_mut().unwrap().add_text(schema_fields.word, keb);
-        }
-        "reb" => {
-            let reb = extract_next_string(parser);
-            context.current_entry
-                .as_mut()
-                .unwrap()
-                .add_text(schema_fields.reading, reb.clone());
-            context.current_entry
-                .as_mut()
-                .unwrap()
-                .add_text(schema_fields.reading_romaji, reb.to_romaji());
-        }
         "gloss" => {
-            let gloss = extract_next_string(parser);
-            context.glosses.push(gloss);
-        }
-        "pos" => {
-            let pos_value = extract_next_string(parser);
-            context.poses.push(pos_value);
-        }
-        "field" => {
-            let field_value = extract_next_string(parser);
-            context.fields.push(field_value);
+            context.current_gloss_lang = attributes
+                .iter()
+                .find(|attr| attr.name.local_name == "lang")
+                .map_or("eng", |attr| attr.value.as_str())
+                .to_string();
         }
         _ => {}
     }
 }
 
+/// Flushes `buffer` (the text of the element that just closed) into
+/// `context`, keyed on the stack path rather than the bare tag name.
+/// Returns `true` when a `<sense>` just closed, signalling the caller to
+/// clear its per-sense accumulators.
 fn handle_end_element(
     element_name: &str,
+    stack: &[String],
+    buffer: &str,
     context: &mut ParseContext,
     index_writer: &mut tantivy::IndexWriter,
     schema_fields: &SchemaFields,
+    scope: IndexScope,
+    examples: Option<&HashMap<String, Vec<Example>>>,
 ) -> Result<bool> {
     match element_name {
         "entry" => {
-            let current_doc = context.current_entry.take().unwrap();
+            let mut current_doc = context.current_entry.take().unwrap();
+
+            if scope == IndexScope::CommonOnly && context.priority == 0 {
+                return Ok(false);
+            }
+
+            current_doc.add_i64(schema_fields.frequency, context.priority);
+            for example in linked_examples(&context.surface_forms, examples) {
+                current_doc.add_text(schema_fields.examples, &example.ja);
+                current_doc.add_text(schema_fields.examples_en, &example.en);
+            }
             index_writer.add_document(current_doc)?;
             context.count += 1;
 
@@ -221,12 +418,71 @@ fn handle_end_element(
         }
         "sense" => {
             if let Some(entry) = context.current_entry.as_mut() {
-                entry.add_text(schema_fields.meaning, context.glosses.join("; "));
+                for (lang, glosses) in &context.glosses {
+                    if let Some(&field) = schema_fields.meaning.get(lang) {
+                        entry.add_text(field, glosses.join("; "));
+                    }
+                }
                 entry.add_text(schema_fields.pos, context.poses.join("; "));
                 entry.add_text(schema_fields.field, context.fields.join("; "));
             }
             Ok(true)
         }
+        "ent_seq" if stack.last().map(String::as_str) == Some("entry") => {
+            context
+                .current_entry
+                .as_mut()
+                .unwrap()
+                .add_i64(schema_fields.id, buffer.parse::<i64>().unwrap());
+            Ok(false)
+        }
+        "keb" if stack.last().map(String::as_str) == Some("k_ele") => {
+            context
+                .current_entry
+                .as_mut()
+                .unwrap()
+                .add_text(schema_fields.word, buffer);
+            context.surface_forms.push(buffer.to_string());
+            Ok(false)
+        }
+        "reb" if stack.last().map(String::as_str) == Some("r_ele") => {
+            context
+                .current_entry
+                .as_mut()
+                .unwrap()
+                .add_text(schema_fields.reading, buffer);
+            context
+                .current_entry
+                .as_mut()
+                .unwrap()
+                .add_text(schema_fields.reading_romaji, buffer.to_romaji());
+            context.surface_forms.push(buffer.to_string());
+            Ok(false)
+        }
+        "gloss" if stack.last().map(String::as_str) == Some("sense") => {
+            context
+                .glosses
+                .entry(std::mem::take(&mut context.current_gloss_lang))
+                .or_default()
+                .push(buffer.to_string());
+            Ok(false)
+        }
+        "pos" if stack.last().map(String::as_str) == Some("sense") => {
+            context.poses.push(buffer.to_string());
+            Ok(false)
+        }
+        "field" if stack.last().map(String::as_str) == Some("sense") => {
+            context.fields.push(buffer.to_string());
+            Ok(false)
+        }
+        "ke_pri" | "re_pri" => {
+            context.priority = context.priority.max(priority_rank(buffer));
+            Ok(false)
+        }
+        // `<re_restr>`/`<stagk>`/`<stagr>` scope a reading to specific
+        // kanji forms; surface form and meaning indexing don't depend on
+        // them, so the stack keeps their text from bleeding into `keb`,
+        // `reb`, or sense fields and they're otherwise dropped here.
         _ => Ok(false),
     }
 }
@@ -242,30 +498,6 @@ fn commit_index(mut index_writer: tantivy::IndexWriter, count: i32) -> Result<()
     Ok(())
 }
 
-fn extract_next_string<R: Read>(parser: &mut EventReader<R>) -> String {
-    let mut buf = String::new();
-    loop {
-        match parser.next().unwrap() {
-            XmlEvent::Characters(s) => {
-                buf.push_str(&s);
-            }
-            XmlEvent::EndElement { name } => {
-                if name.local_name == "keb"
-                    || name.local_name == "reb"
-                    || name.local_name == "gloss"
-                    || name.local_name == "pos"
-                    || name.local_name == "field"
-                    || name.local_name == "ent_seq"
-                {
-                    break;
-                }
-            }
-            _ => {}
-        }
-    }
-    buf
-}
-
 pub fn fetch_jmdict<P: AsRef<Path>>(url: &str, out_file: P) -> Result<()> {
     println!("Downloading JMdict from {url}...");
     let mut resp = reqwest::blocking::get(url)?;
@@ -275,43 +507,120 @@ pub fn fetch_jmdict<P: AsRef<Path>>(url: &str, out_file: P) -> Result<()> {
     Ok(())
 }
 
+pub fn fetch_tatoeba<P: AsRef<Path>>(url: &str, out_file: P) -> Result<()> {
+    println!("Downloading Tatoeba sentence pairs from {url}...");
+    let mut resp = reqwest::blocking::get(url)?;
+    let mut out = File::create(out_file)?;
+    io::copy(&mut resp, &mut out)?;
+    println!("Download complete.");
+    Ok(())
+}
+
+/// Reads Tatoeba's gzipped Japanese/English sentence pairs file (one
+/// tab-separated `ja_id\tja_text\ten_id\ten_text` record per line) and
+/// indexes example sentences by the surface forms that appear among their
+/// Lindera-segmented tokens, so `create_index` can look them up by `keb`
+/// and `reb` while building each entry. Each surface form keeps only its
+/// `MAX_EXAMPLES_PER_ENTRY` shortest matching sentences.
+pub fn load_examples(path: &str) -> Result<HashMap<String, Vec<Example>>> {
+    let dictionary = load_dictionary_from_kind(DictionaryKind::IPADIC)?;
+    let segmenter = Segmenter::new(Mode::Normal, dictionary, None);
+
+    let file = File::open(path)?;
+    let reader = BufReader::new(GzDecoder::new(file));
+
+    let mut examples: HashMap<String, Vec<Example>> = HashMap::new();
+    for line in reader.lines() {
+        let line = line?;
+        let mut columns = line.split('\t');
+        let (Some(_ja_id), Some(ja_text), Some(_en_id), Some(en_text)) =
+            (columns.next(), columns.next(), columns.next(), columns.next())
+        else {
+            continue;
+        };
+
+        for token in segmenter.segment(ja_text)? {
+            examples
+                .entry(token.text.into_owned())
+                .or_default()
+                .push(Example {
+                    ja: ja_text.to_string(),
+                    en: en_text.to_string(),
+                });
+        }
+    }
+
+    for bucket in examples.values_mut() {
+        bucket.sort_by_key(|example| example.ja.chars().count());
+        bucket.dedup_by(|a, b| a.ja == b.ja);
+        bucket.truncate(MAX_EXAMPLES_PER_ENTRY);
+    }
+
+    Ok(examples)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
     #[test]
-    fn test_extract_next_string() {
-        let mut parser = EventReader::from_str(
-            r"
+    fn test_multi_sense_and_reading_entry() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let xml = r"<JMdict>
             <entry>
                 <ent_seq>1</ent_seq>
-                <k_ele>
-                    <keb>日本</keb>
-                </k_ele>
-                <r_ele>
-                    <reb>にほん</reb>
-                </r_ele>
+                <k_ele><keb>日本</keb></k_ele>
+                <r_ele><reb>にほん</reb></r_ele>
+                <r_ele><reb>にっぽん</reb></r_ele>
                 <sense>
-                    <gloss>Japan</gloss>
-                    <gloss>Japanese</gloss>
                     <pos>noun</pos>
-                    <pos>proper noun</pos>
-                    <field>place</field>
-                    <field>country</field>
+                    <gloss>Japan</gloss>
+                </sense>
+                <sense>
+                    <pos>adjective</pos>
+                    <gloss>Japanese-style</gloss>
                 </sense>
             </entry>
-        ",
-        );
-
-        assert_eq!(extract_next_string(&mut parser), "1");
-        assert_eq!(extract_next_string(&mut parser), "日本");
-        assert_eq!(extract_next_string(&mut parser), "にほん");
-        assert_eq!(extract_next_string(&mut parser), "Japan");
-        assert_eq!(extract_next_string(&mut parser), "Japanese");
-        assert_eq!(extract_next_string(&mut parser), "noun");
-        assert_eq!(extract_next_string(&mut parser), "proper noun");
-        assert_eq!(extract_next_string(&mut parser), "place");
-        assert_eq!(extract_next_string(&mut parser), "country");
+        </JMdict>";
+
+        let source_dir = tempfile::tempdir().unwrap();
+        let gz_path = source_dir.path().join("test.gz");
+        let mut encoder = GzEncoder::new(File::create(&gz_path).unwrap(), Compression::default());
+        encoder.write_all(xml.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let index_path = tempfile::tempdir().unwrap();
+        let schema = create_schema(DEFAULT_LANGUAGES);
+        let index = Index::create_in_dir(index_path.path(), schema.clone()).unwrap();
+        create_index(
+            &schema,
+            gz_path.to_str().unwrap(),
+            &index,
+            IndexScope::All,
+            None,
+            &TokenizerConfig::default(),
+        )
+        .unwrap();
+
+        let searcher = index.reader().unwrap().searcher();
+        assert_eq!(searcher.num_docs(), 1);
+
+        let doc: TantivyDocument = searcher.doc(tantivy::DocAddress::new(0, 0)).unwrap();
+        let reading_field = schema.get_field("reading").unwrap();
+        let readings: Vec<_> = doc
+            .get_all(reading_field)
+            .filter_map(|v| v.as_str())
+            .collect();
+        assert_eq!(readings, vec!["にほん", "にっぽん"]);
+
+        let meaning_field = schema.get_field("meaning_eng").unwrap();
+        let meanings: Vec<_> = doc
+            .get_all(meaning_field)
+            .filter_map(|v| v.as_str())
+            .collect();
+        assert_eq!(meanings, vec!["Japan", "Japanese-style"]);
     }
 
     #[test]
@@ -319,8 +628,16 @@ mod test {
         // download jmdict_e if not present
         let jmdict_path = Path::new("testdata/JMdict_e_test.gz");
         let index_path = tempfile::tempdir().unwrap();
-        let schema = create_schema();
+        let schema = create_schema(DEFAULT_LANGUAGES);
         let index = Index::create_in_dir(index_path.path(), schema.clone()).unwrap();
-        create_index(&schema, jmdict_path.to_str().unwrap(), &index).unwrap();
+        create_index(
+            &schema,
+            jmdict_path.to_str().unwrap(),
+            &index,
+            IndexScope::All,
+            None,
+            &TokenizerConfig::default(),
+        )
+        .unwrap();
     }
 }
@@ -0,0 +1,3 @@
+pub mod indexer;
+pub mod query;
+pub mod server;
@@ -0,0 +1,60 @@
+#![deny(clippy::all)]
+#![deny(clippy::pedantic)]
+
+use tantivy::query::{BooleanQuery, Occur, Query, QueryParser};
+use tantivy::schema::{Field, IndexRecordOption};
+use tantivy::Term;
+use wana_kana::ConvertJapanese;
+
+/// A search term is treated as romaji when it's non-empty and made up only
+/// of ASCII letters; mixed-script or kana/kanji input is left for the
+/// Lindera-tokenized path.
+fn is_ascii_romaji(term: &str) -> bool {
+    !term.is_empty() && term.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// Builds the query tantivy runs for a user's search term.
+///
+/// Pure ASCII romaji input (e.g. `nihon`) can't match the `ja_JP`-tokenized
+/// `reading` field directly, so it's expanded via `wana_kana` into its kana
+/// form and searched as a disjunction across the kana `reading` field and
+/// the raw `reading_romaji` field. This lets users who can't type kana
+/// still find entries by their reading. Mixed-script input falls through
+/// untouched to `query_parser`.
+pub fn build_query(
+    query_parser: &QueryParser,
+    reading_field: Field,
+    reading_romaji_field: Field,
+    term: &str,
+) -> tantivy::Result<Box<dyn Query>> {
+    if is_ascii_romaji(term) {
+        let kana = term.to_hiragana();
+        let kana_query: Box<dyn Query> = Box::new(tantivy::query::TermQuery::new(
+            Term::from_field_text(reading_field, &kana),
+            IndexRecordOption::Basic,
+        ));
+        let romaji_query: Box<dyn Query> = Box::new(tantivy::query::TermQuery::new(
+            Term::from_field_text(reading_romaji_field, term),
+            IndexRecordOption::Basic,
+        ));
+        return Ok(Box::new(BooleanQuery::new(vec![
+            (Occur::Should, kana_query),
+            (Occur::Should, romaji_query),
+        ])));
+    }
+
+    query_parser.parse_query(term)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn recognizes_ascii_romaji() {
+        assert!(is_ascii_romaji("nihon"));
+        assert!(!is_ascii_romaji("にほん"));
+        assert!(!is_ascii_romaji("nihon語"));
+        assert!(!is_ascii_romaji(""));
+    }
+}
@@ -0,0 +1,183 @@
+#![deny(clippy::all)]
+#![deny(clippy::pedantic)]
+
+use crate::indexer;
+use crate::indexer::TokenizerConfig;
+use crate::query;
+use anyhow::{Context, Result};
+use axum::extract::{Query, State};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tantivy::collector::TopDocs;
+use tantivy::query::QueryParser;
+use tantivy::schema::{Field, Schema};
+use tantivy::{Index, IndexReader, TantivyDocument};
+
+struct AppState {
+    index: Index,
+    schema: Schema,
+    reader: IndexReader,
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    q: String,
+    lang: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct SearchHit {
+    id: i64,
+    word: String,
+    reading: String,
+    meaning: String,
+    pos: String,
+    examples: Vec<ExampleHit>,
+}
+
+#[derive(Serialize)]
+struct ExampleHit {
+    ja: String,
+    en: String,
+}
+
+/// Opens the index committed at `index_path`, re-registers the `ja_JP`
+/// Lindera tokenizer per `tokenizer_config`, and builds the router backing
+/// the `serve` subcommand.
+pub fn build_router(index_path: &str, tokenizer_config: &TokenizerConfig) -> Result<Router> {
+    let index = Index::open_in_dir(index_path).context("opening index")?;
+    indexer::setup_tokenizer(&index, tokenizer_config)?;
+    let schema = index.schema();
+    let reader = index.reader()?;
+
+    let state = Arc::new(AppState {
+        index,
+        schema,
+        reader,
+    });
+
+    Ok(Router::new()
+        .route("/search", get(search))
+        .route("/analyze", get(analyze))
+        .with_state(state))
+}
+
+/// Runs the `serve` subcommand: binds `addr` and serves the search API
+/// until the process is interrupted.
+pub async fn serve(index_path: &str, addr: &str, tokenizer_config: &TokenizerConfig) -> Result<()> {
+    let router = build_router(index_path, tokenizer_config)?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    println!("Serving search API on {addr}...");
+    axum::serve(listener, router).await?;
+    Ok(())
+}
+
+async fn search(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SearchParams>,
+) -> Json<Vec<SearchHit>> {
+    Json(run_search(&state, &params).unwrap_or_default())
+}
+
+#[derive(Deserialize)]
+struct AnalyzeParams {
+    text: String,
+}
+
+async fn analyze(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AnalyzeParams>,
+) -> Json<Vec<indexer::AnalyzedToken>> {
+    Json(indexer::analyze(&state.index, &params.text).unwrap_or_default())
+}
+
+fn run_search(state: &AppState, params: &SearchParams) -> Result<Vec<SearchHit>> {
+    let lang = params.lang.as_deref().unwrap_or("eng");
+    let fields = query_fields(&state.schema, lang)?;
+    let query_parser = QueryParser::for_index(&state.index, fields);
+    let reading_field = state.schema.get_field("reading")?;
+    let reading_romaji_field = state.schema.get_field("reading_romaji")?;
+    let query = query::build_query(&query_parser, reading_field, reading_romaji_field, &params.q)?;
+
+    let searcher = state.reader.searcher();
+    let limit = params.limit.unwrap_or(10);
+    let collector =
+        TopDocs::with_limit(limit).tweak_score(move |segment_reader: &tantivy::SegmentReader| {
+            let frequencies = segment_reader.fast_fields().i64("frequency").unwrap();
+            move |doc: tantivy::DocId, original_score: tantivy::Score| {
+                let frequency = frequencies.values_for_doc(doc).next().unwrap_or(0);
+                // Everyday words (high `frequency`) should outrank obscure
+                // entries with an equivalent text match.
+                #[allow(clippy::cast_precision_loss)]
+                let boost = 1.0 + frequency as f32 / 50.0;
+                original_score * boost
+            }
+        });
+    let top_docs = searcher.search(query.as_ref(), &collector)?;
+
+    let id_field = state.schema.get_field("id")?;
+    let word_field = state.schema.get_field("word")?;
+    let reading_field = state.schema.get_field("reading")?;
+    let meaning_field = meaning_field(&state.schema, lang)?;
+    let pos_field = state.schema.get_field("pos")?;
+    let examples_field = state.schema.get_field("examples")?;
+    let examples_en_field = state.schema.get_field("examples_en")?;
+
+    top_docs
+        .into_iter()
+        .map(|(_score, doc_address)| {
+            let doc: TantivyDocument = searcher.doc(doc_address)?;
+            let examples = text_values(&doc, examples_field)
+                .into_iter()
+                .zip(text_values(&doc, examples_en_field))
+                .map(|(ja, en)| ExampleHit { ja, en })
+                .collect();
+            Ok(SearchHit {
+                id: doc
+                    .get_first(id_field)
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or_default(),
+                word: text_value(&doc, word_field),
+                reading: text_value(&doc, reading_field),
+                meaning: text_value(&doc, meaning_field),
+                pos: text_value(&doc, pos_field),
+                examples,
+            })
+        })
+        .collect()
+}
+
+/// The fields searched by the `q` query: the Japanese surface forms, the
+/// romaji reading, and the meaning field for the requested `lang`.
+fn query_fields(schema: &Schema, lang: &str) -> Result<Vec<Field>> {
+    Ok(vec![
+        schema.get_field("word")?,
+        schema.get_field("reading")?,
+        schema.get_field("reading_romaji")?,
+        meaning_field(schema, lang)?,
+    ])
+}
+
+fn meaning_field(schema: &Schema, lang: &str) -> Result<Field> {
+    schema
+        .get_field(&format!("meaning_{lang}"))
+        .or_else(|_| schema.get_field("meaning_eng"))
+        .context("schema has no meaning field for the requested language")
+}
+
+fn text_value(doc: &TantivyDocument, field: Field) -> String {
+    doc.get_first(field)
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn text_values(doc: &TantivyDocument, field: Field) -> Vec<String> {
+    doc.get_all(field)
+        .filter_map(|v| v.as_str())
+        .map(str::to_string)
+        .collect()
+}